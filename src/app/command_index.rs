@@ -0,0 +1,115 @@
+use std::collections::BTreeSet;
+use std::env;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+
+/// The separators that end a pipeline stage. The token immediately following
+/// one of these (or the first token of the line) is in *command position* and
+/// should be completed against `$PATH` rather than the filesystem.
+const STAGE_SEPARATORS: &[char] = &['|', ';', '&'];
+
+/// An index of executable basenames discovered by scanning every directory in
+/// `$PATH`. Built once and cached on the [`App`](super::app::App); dropped
+/// and rebuilt on the next use after a `:reload-config`, so executables added
+/// to the `$PATH` directories since the last scan are reflected in completion.
+#[derive(Debug, Clone)]
+pub struct ExecutableIndex {
+    names: BTreeSet<String>,
+}
+
+impl ExecutableIndex {
+    /// Scan every directory in `$PATH` and collect the basenames of all
+    /// executable files.
+    pub fn build() -> ExecutableIndex {
+        let mut names = BTreeSet::new();
+        if let Some(path) = env::var_os("PATH") {
+            for dir in env::split_paths(&path) {
+                let entries = match dir.read_dir() {
+                    Ok(entries) => entries,
+                    Err(_) => continue,
+                };
+                for entry in entries.filter_map(|entry| entry.ok()) {
+                    if is_executable(&entry) {
+                        names.insert(entry.file_name().to_string_lossy().to_string());
+                    }
+                }
+            }
+        }
+        ExecutableIndex { names }
+    }
+
+    /// Every executable basename starting with `prefix`.
+    pub fn matching(&self, prefix: &str) -> Vec<String> {
+        self.names
+            .iter()
+            .filter(|name| name.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Whether the entry is a regular file with an executable bit set. On
+/// non-unix platforms every file is considered a candidate.
+///
+/// Uses `std::fs::metadata` rather than `DirEntry::metadata`, which does not
+/// follow symlinks — that would drop every `$PATH` entry that is a symlink,
+/// e.g. Debian's `update-alternatives` shims or pyenv/rbenv/asdf shims.
+fn is_executable(entry: &std::fs::DirEntry) -> bool {
+    match std::fs::metadata(entry.path()) {
+        Ok(metadata) if metadata.is_file() => executable_bit(&metadata),
+        _ => false,
+    }
+}
+
+#[cfg(unix)]
+fn executable_bit(metadata: &std::fs::Metadata) -> bool {
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn executable_bit(_metadata: &std::fs::Metadata) -> bool {
+    true
+}
+
+/// Whether the word ending at `prefix` (the line content before the hovered
+/// word) sits in command position: the first token of the line, or the token
+/// immediately following a `|`, `;`, `&&` or `||` (a lone trailing `&` counts
+/// as well).
+pub fn in_command_position(prefix: &str) -> bool {
+    let trimmed = prefix.trim_end();
+    trimmed.is_empty() || trimmed.ends_with(STAGE_SEPARATORS)
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::symlink;
+
+    /// `DirEntry::metadata` is an `lstat` of the entry itself, so a symlinked
+    /// executable (as `update-alternatives`/pyenv/rbenv shims commonly are)
+    /// must still be picked up once we follow the link.
+    #[test]
+    fn symlinked_executable_is_still_detected() {
+        let dir = env::temp_dir().join(format!("pipr-exec-index-test-{}-{}", std::process::id(), line!()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let real = dir.join("real-bin");
+        fs::write(&real, b"#!/bin/sh\n").unwrap();
+        let mut perms = fs::metadata(&real).unwrap().permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&real, perms).unwrap();
+
+        let link = dir.join("linked-bin");
+        symlink(&real, &link).unwrap();
+
+        let entry = fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .find(|entry| entry.file_name() == "linked-bin")
+            .unwrap();
+        assert!(is_executable(&entry));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
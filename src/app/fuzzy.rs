@@ -0,0 +1,234 @@
+use crate::commandlist::{CommandEntry, CommandList};
+
+const MATCH_SCORE: i32 = 16;
+const CONSECUTIVE_BONUS: i32 = 8;
+const BOUNDARY_BONUS: i32 = 8;
+const GAP_PENALTY: i32 = 1;
+
+/// Characters that start a new "word" for the word-boundary bonus.
+fn is_separator(c: char) -> bool {
+    matches!(c, ' ' | '/' | '-' | '_' | '.')
+}
+
+/// The result of scoring a single candidate against a query: the accumulated
+/// `score` and the candidate character indices that were matched (for
+/// highlighting in the overlay).
+#[derive(Debug, Clone)]
+pub struct FuzzyMatch {
+    pub score: i32,
+    pub indices: Vec<usize>,
+}
+
+/// Score `candidate` against `query` using a self-contained subsequence
+/// matcher. A query matches if every query char appears, in order and
+/// case-insensitively, in the candidate. Consecutive matches and matches on a
+/// word boundary are rewarded; gap characters between matches are penalised.
+/// Returns `None` when the query is not a subsequence of the candidate.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let chars: Vec<char> = candidate.chars().collect();
+    let lowered: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    if query.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            indices: Vec::new(),
+        });
+    }
+
+    let mut qi = 0;
+    let mut score = 0;
+    let mut indices = Vec::with_capacity(query.len());
+    let mut last_match: Option<usize> = None;
+
+    for (ci, &c) in lowered.iter().enumerate() {
+        if qi >= query.len() || c != query[qi] {
+            continue;
+        }
+
+        score += MATCH_SCORE;
+        match last_match {
+            Some(previous) if ci == previous + 1 => score += CONSECUTIVE_BONUS,
+            Some(previous) => score -= GAP_PENALTY * (ci - previous - 1) as i32,
+            None => {}
+        }
+        if ci == 0 || is_separator(chars[ci - 1]) {
+            score += BOUNDARY_BONUS;
+        }
+
+        indices.push(ci);
+        last_match = Some(ci);
+        qi += 1;
+    }
+
+    if qi == query.len() {
+        Some(FuzzyMatch { score, indices })
+    } else {
+        None
+    }
+}
+
+/// Which list the fuzzy overlay is currently searching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuzzySource {
+    History,
+    Bookmarks,
+}
+
+impl FuzzySource {
+    fn toggled(self) -> FuzzySource {
+        match self {
+            FuzzySource::History => FuzzySource::Bookmarks,
+            FuzzySource::Bookmarks => FuzzySource::History,
+        }
+    }
+}
+
+/// A single scored entry shown in the overlay.
+#[derive(Debug, Clone)]
+pub struct FuzzyEntry {
+    pub entry: CommandEntry,
+    pub text: String,
+    pub indices: Vec<usize>,
+}
+
+/// State backing the interactive fuzzy-search overlay bound to Ctrl-R. Filters
+/// a [`CommandList`] live as the user types and keeps the results sorted by
+/// descending score.
+#[derive(Debug)]
+pub struct FuzzyFinder {
+    pub query: String,
+    pub source: FuzzySource,
+    pub results: Vec<FuzzyEntry>,
+    pub selected: usize,
+}
+
+impl FuzzyFinder {
+    pub fn new() -> FuzzyFinder {
+        FuzzyFinder {
+            query: String::new(),
+            source: FuzzySource::History,
+            results: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// Toggle between searching the history and the bookmarks.
+    pub fn toggle_source(&mut self) {
+        self.source = self.source.toggled();
+    }
+
+    /// Recompute the result list from the two command lists. Candidates that
+    /// match are sorted by descending score, ties broken by shorter candidate
+    /// length and then recency (later entries first).
+    pub fn refresh(&mut self, history: &CommandList, bookmarks: &CommandList) {
+        let list = match self.source {
+            FuzzySource::History => history,
+            FuzzySource::Bookmarks => bookmarks,
+        };
+
+        let mut scored: Vec<(i32, usize, FuzzyEntry)> = Vec::new();
+        for idx in 0..list.len() {
+            let entry = match list.get_at(idx) {
+                Some(entry) => entry,
+                None => continue,
+            };
+            let text = entry.command.join(" ");
+            if let Some(matched) = fuzzy_match(&self.query, &text) {
+                scored.push((
+                    matched.score,
+                    idx,
+                    FuzzyEntry {
+                        entry: entry.clone(),
+                        indices: matched.indices,
+                        text,
+                    },
+                ));
+            }
+        }
+
+        // score descending, then shorter candidate first, then recency. The
+        // lists are append-only, so a higher index is the more recent entry.
+        scored.sort_by(|(a_score, a_idx, a), (b_score, b_idx, b)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| a.text.len().cmp(&b.text.len()))
+                .then_with(|| b_idx.cmp(a_idx))
+        });
+
+        self.results = scored.into_iter().map(|(_, _, entry)| entry).collect();
+        self.selected = self.selected.min(self.results.len().saturating_sub(1));
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.results.is_empty() {
+            self.selected = (self.selected + 1) % self.results.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.results.is_empty() {
+            self.selected = if self.selected == 0 {
+                self.results.len() - 1
+            } else {
+                self.selected - 1
+            };
+        }
+    }
+
+    /// The currently highlighted entry, if any.
+    pub fn selected(&self) -> Option<&FuzzyEntry> {
+        self.results.get(self.selected)
+    }
+}
+
+impl Default for FuzzyFinder {
+    fn default() -> FuzzyFinder {
+        FuzzyFinder::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty_query_matches_with_zero_score() {
+        let matched = fuzzy_match("", "anything").unwrap();
+        assert_eq!(matched.score, 0);
+        assert!(matched.indices.is_empty());
+    }
+
+    #[test]
+    fn non_subsequence_does_not_match() {
+        assert!(fuzzy_match("xyz", "grep foo").is_none());
+        // out of order
+        assert!(fuzzy_match("og", "foo").is_none());
+    }
+
+    #[test]
+    fn matching_is_case_insensitive() {
+        let matched = fuzzy_match("GREP", "grep foo").unwrap();
+        assert_eq!(matched.indices, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn consecutive_match_beats_scattered_match() {
+        let consecutive = fuzzy_match("cat", "cat file").unwrap().score;
+        let scattered = fuzzy_match("cat", "c a t").unwrap().score;
+        assert!(consecutive > scattered);
+    }
+
+    #[test]
+    fn word_boundary_match_beats_mid_word_match() {
+        let boundary = fuzzy_match("f", "a f").unwrap().score;
+        let mid_word = fuzzy_match("f", "af").unwrap().score;
+        assert!(boundary > mid_word);
+    }
+
+    #[test]
+    fn records_matched_indices() {
+        let matched = fuzzy_match("ac", "abc").unwrap();
+        assert_eq!(matched.indices, vec![0, 2]);
+    }
+}
@@ -1,19 +1,33 @@
 use super::app::*;
+use super::command_index::{in_command_position, ExecutableIndex};
+use super::fuzzy::FuzzyFinder;
 use super::key_select_menu::KeySelectMenu;
+use super::typable_commands;
 use super::util::*;
 use super::{lineeditor::*, Path};
 use crossterm::event::{KeyCode, KeyModifiers};
 use std::path::PathBuf;
 
+/// Which buffer a pending [`AutocompleteState`] will splice its chosen
+/// completion into once accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutocompleteTarget {
+    /// The main multi-line input, completed via Tab.
+    Input,
+    /// The `:`-prefixed command palette line, completed via Tab while it's open.
+    CommandLine,
+}
+
 #[derive(Debug)]
 pub struct AutocompleteState {
     pub original_prompt: String,
     pub options: Vec<String>,
     pub current_idx: usize,
+    pub target: AutocompleteTarget,
 }
 
 impl AutocompleteState {
-    fn from_options(original_prompt: String, options: Vec<String>) -> Option<AutocompleteState> {
+    fn from_options(original_prompt: String, options: Vec<String>, target: AutocompleteTarget) -> Option<AutocompleteState> {
         if options.is_empty() {
             None
         } else {
@@ -21,6 +35,7 @@ impl AutocompleteState {
                 current_idx: 0,
                 original_prompt,
                 options,
+                target,
             })
         }
     }
@@ -56,6 +71,137 @@ impl App {
         }
     }
 
+    /// Handle a keypress while the `:`-prefixed command palette is open. Enter
+    /// parses the line into a command name plus arguments and dispatches it,
+    /// Esc cancels, and Tab offers argument completion via the command's
+    /// declared [`Completer`].
+    fn handle_command_line_event(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => self.command_line = None,
+            KeyCode::Backspace => {
+                if let Some(line) = self.command_line.as_mut() {
+                    line.pop();
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Some(line) = self.command_line.as_mut() {
+                    line.push(c);
+                }
+            }
+            KeyCode::Tab => self.complete_command_line(),
+            KeyCode::Enter => {
+                if let Some(line) = self.command_line.take() {
+                    if let Err(err) = typable_commands::dispatch(self, &line) {
+                        self.set_status(err.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle a keypress while the per-stage pipeline view is open. Up/Down
+    /// move the cut point along the pipeline to inspect the output at that
+    /// point; Esc or F4 closes the view. The view shows a snapshot frozen at
+    /// the moment it was opened, so — like the command palette and fuzzy
+    /// finder — it consumes every key while open rather than letting keys
+    /// fall through to edit the buffer underneath it out from under the
+    /// snapshot.
+    fn handle_pipeline_view_event(&mut self, code: KeyCode) {
+        let view = match self.pipeline_view.as_mut() {
+            Some(view) => view,
+            None => return,
+        };
+        match code {
+            KeyCode::Up => view.move_cut_up(),
+            KeyCode::Down => view.move_cut_down(),
+            KeyCode::Esc | KeyCode::F(4) => self.pipeline_view = None,
+            _ => {}
+        }
+    }
+
+    /// The `$PATH` executable index, built on first use and cached until the
+    /// next `:reload-config`.
+    fn executable_index(&mut self) -> &ExecutableIndex {
+        self.executable_index.get_or_insert_with(ExecutableIndex::build)
+    }
+
+    /// Re-read `pipr.toml` from disk and drop the cached `$PATH` executable
+    /// index, so executables added to the `$PATH` directories since the last
+    /// scan show up in completion. Already running plugins are not restarted.
+    /// Bound to the `:reload-config` typable command.
+    pub fn reload_config(&mut self) {
+        self.config = crate::pipr_config::PiprConfig::load_from_file();
+        self.executable_index = None;
+    }
+
+    /// Handle a keypress while the Ctrl-R fuzzy finder overlay is open. The
+    /// query filters the selected [`CommandList`](crate::commandlist::CommandList)
+    /// live; Enter loads the highlighted entry, Tab toggles between history and
+    /// bookmarks, and Esc closes the overlay.
+    fn handle_fuzzy_finder_event(&mut self, code: KeyCode) {
+        let mut finder = match self.fuzzy_finder.take() {
+            Some(finder) => finder,
+            None => return,
+        };
+        match code {
+            KeyCode::Esc => return,
+            KeyCode::Up => finder.select_prev(),
+            KeyCode::Down => finder.select_next(),
+            KeyCode::Tab => {
+                finder.toggle_source();
+                finder.refresh(&self.history, &self.bookmarks);
+            }
+            KeyCode::Enter => {
+                if let Some(selected) = finder.selected() {
+                    self.input_state.load_commandentry(&selected.entry);
+                }
+                return;
+            }
+            KeyCode::Backspace => {
+                finder.query.pop();
+                finder.refresh(&self.history, &self.bookmarks);
+            }
+            KeyCode::Char(c) => {
+                finder.query.push(c);
+                finder.refresh(&self.history, &self.bookmarks);
+            }
+            _ => {}
+        }
+        self.fuzzy_finder = Some(finder);
+    }
+
+    /// Offer completion for the word currently being typed in the command
+    /// palette, reusing the main autocomplete-cycling overlay.
+    fn complete_command_line(&mut self) {
+        let line = match &self.command_line {
+            Some(line) => line.clone(),
+            None => return,
+        };
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap_or("");
+        let word = line.split_whitespace().last().filter(|_| line.ends_with(|c: char| !c.is_whitespace()));
+
+        let completer = if words.next().is_none() && !line.contains(char::is_whitespace) {
+            // still typing the command name itself
+            None
+        } else {
+            typable_commands::lookup(name).and_then(|command| command.completer)
+        };
+
+        let options = match completer {
+            Some(completer) => completer.options(self, word.unwrap_or("")),
+            None => typable_commands::COMMANDS
+                .iter()
+                .map(|command| command.name.to_string())
+                .filter(|candidate| candidate.starts_with(name))
+                .collect(),
+        };
+
+        let prompt = word.unwrap_or("").to_string();
+        self.autocomplete_state = AutocompleteState::from_options(prompt, options, AutocompleteTarget::CommandLine);
+    }
+
     pub async fn handle_main_window_tui_event(&mut self, code: KeyCode, modifiers: KeyModifiers) {
         let control_pressed = modifiers.contains(KeyModifiers::CONTROL);
 
@@ -71,9 +217,18 @@ impl App {
                 }
                 KeyCode::Enter => {
                     let chosen_completion = autocomplete_state.selected();
-                    let completed_value = chosen_completion.trim_start_matches(&autocomplete_state.original_prompt);
-                    self.input_state.insert_at_cursor(completed_value);
-                    self.input_state.cursor_col += completed_value.len();
+                    let completed_value = chosen_completion.trim_start_matches(&autocomplete_state.original_prompt).to_string();
+                    match autocomplete_state.target {
+                        AutocompleteTarget::Input => {
+                            self.input_state.insert_at_cursor(&completed_value);
+                            self.input_state.cursor_col += completed_value.len();
+                        }
+                        AutocompleteTarget::CommandLine => {
+                            if let Some(line) = self.command_line.as_mut() {
+                                line.push_str(&completed_value);
+                            }
+                        }
+                    }
                     self.autocomplete_state = None;
                     return;
                 }
@@ -90,7 +245,43 @@ impl App {
             return;
         }
 
+        if self.command_line.is_some() {
+            self.handle_command_line_event(code);
+            return;
+        }
+
+        if self.fuzzy_finder.is_some() {
+            self.handle_fuzzy_finder_event(code);
+            return;
+        }
+
+        if self.pipeline_view.is_some() {
+            self.handle_pipeline_view_event(code);
+            return;
+        }
+
         match code {
+            // Only open the command palette when `:` can't be ordinary content:
+            // on an empty line. Anywhere else a literal colon is typed normally
+            // (e.g. `docker run img:tag`, `awk -F:`, `date +%H:%M`).
+            KeyCode::Char(':') if self.input_state.current_line().is_empty() => self.command_line = Some(String::new()),
+            // Only reached when no view is open yet (an open view's F4 is
+            // handled by `handle_pipeline_view_event`, which closes it).
+            KeyCode::F(4) => {
+                let mut view = crate::pipeline::PipelineView::new(&self.input_state.content_str());
+                let isolation_mounts = if self.isolation_enabled {
+                    Some(self.config.isolation_mounts_readonly.clone())
+                } else {
+                    None
+                };
+                view.evaluate(self.config.eval_environment.clone(), isolation_mounts);
+                self.pipeline_view = Some(view);
+            }
+            KeyCode::Char('r') if control_pressed => {
+                let mut finder = FuzzyFinder::new();
+                finder.refresh(&self.history, &self.bookmarks);
+                self.fuzzy_finder = Some(finder);
+            }
             KeyCode::Esc => self.set_should_quit(),
             KeyCode::Char('q') | KeyCode::Char('c') if control_pressed => self.set_should_quit(),
             KeyCode::F(2) => self.autoeval_mode = !self.autoeval_mode,
@@ -102,14 +293,24 @@ impl App {
                 let hovered_char = self.input_state.hovered_char();
                 if hovered_char.is_none() || hovered_char == Some(" ") || hovered_char == Some("") {
                     if let Some(hovered_word) = hovered_word {
-                        if let Some(completions) = provide_path_autocomplete(hovered_word) {
+                        let prefix_len = self.input_state.cursor_col.saturating_sub(hovered_word.len());
+                        let prefix = current_line.get(..prefix_len).unwrap_or("");
+                        let mut completions = if in_command_position(prefix) {
+                            self.executable_index().matching(hovered_word)
+                        } else {
+                            provide_path_autocomplete(hovered_word).unwrap_or_default()
+                        };
+                        self.plugins
+                            .autocomplete(&current_line, self.input_state.cursor_col, hovered_word, &mut completions);
+                        if !completions.is_empty() {
                             if completions.len() == 1 {
                                 let completed_value = completions.first().unwrap();
                                 let completed_value = completed_value.trim_start_matches(hovered_word);
                                 self.input_state.insert_at_cursor(completed_value);
                                 self.input_state.cursor_col += completed_value.len();
                             } else if completions.len() > 1 {
-                                self.autocomplete_state = AutocompleteState::from_options(hovered_word.to_string(), completions);
+                                self.autocomplete_state =
+                                    AutocompleteState::from_options(hovered_word.to_string(), completions, AutocompleteTarget::Input);
                             }
                         }
                     }
@@ -153,8 +354,14 @@ impl App {
                     let previous_content = self.input_state.content_str();
                     self.input_state.apply_event(editor_event);
 
-                    if self.autoeval_mode && previous_content != self.input_state.content_str() {
-                        self.execute_content().await;
+                    let current_content = self.input_state.content_str();
+                    if previous_content != current_content {
+                        // Linting runs on a background thread and is picked up later via
+                        // `poll_lint`, so a hung or slow plugin never stalls this keystroke.
+                        self.plugins.request_lint(current_content);
+                        if self.autoeval_mode {
+                            self.execute_content().await;
+                        }
                     }
                 }
             }
@@ -189,7 +396,7 @@ impl App {
     }
 }
 
-fn provide_path_autocomplete(word: &str) -> Option<Vec<String>> {
+pub(crate) fn provide_path_autocomplete(word: &str) -> Option<Vec<String>> {
     let mut path = PathBuf::new();
     path.push(word);
 
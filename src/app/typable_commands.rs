@@ -0,0 +1,248 @@
+use super::app::*;
+use super::main_window::provide_path_autocomplete;
+use std::fs::File;
+use std::io::{Read, Write};
+
+/// The argument completer a typable command offers for its arguments. Each
+/// variant feeds the existing [`AutocompleteState`](super::main_window::AutocompleteState)
+/// cycling machinery through [`Completer::options`].
+#[derive(Debug, Clone, Copy)]
+pub enum Completer {
+    Filename,
+    SnippetKey,
+    BoolFlag,
+    None,
+}
+
+impl Completer {
+    /// Produce the candidate list for the word currently being typed.
+    pub fn options(&self, app: &App, word: &str) -> Vec<String> {
+        match self {
+            Completer::Filename => provide_path_autocomplete(word).unwrap_or_default(),
+            Completer::SnippetKey => app
+                .config
+                .snippets
+                .keys()
+                .map(|key| key.to_string())
+                .filter(|key| key.starts_with(word))
+                .collect(),
+            Completer::BoolFlag => ["on", "off"]
+                .iter()
+                .map(|flag| flag.to_string())
+                .filter(|flag| flag.starts_with(word))
+                .collect(),
+            Completer::None => Vec::new(),
+        }
+    }
+}
+
+/// A command typable in the `:`-prefixed command palette. The `fun` receives
+/// the parsed, whitespace-separated arguments and the owning [`App`].
+pub struct TypableCommand {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub doc: &'static str,
+    pub fun: fn(&mut App, &[&str]) -> Result<(), failure::Error>,
+    pub completer: Option<Completer>,
+}
+
+impl TypableCommand {
+    /// Whether `name` matches this command's canonical name or any alias.
+    pub fn matches(&self, name: &str) -> bool {
+        self.name == name || self.aliases.contains(&name)
+    }
+}
+
+/// The static registry of every typable command, each with a short `doc`
+/// string describing it.
+pub static COMMANDS: &[TypableCommand] = &[
+    TypableCommand {
+        name: "write",
+        aliases: &["w"],
+        doc: "write the current content to a file",
+        fun: cmd_write,
+        completer: Some(Completer::Filename),
+    },
+    TypableCommand {
+        name: "read",
+        aliases: &["r", "e"],
+        doc: "replace the content with a file's contents",
+        fun: cmd_read,
+        completer: Some(Completer::Filename),
+    },
+    TypableCommand {
+        name: "set",
+        aliases: &[],
+        doc: "set a boolean option (autoeval|paranoid on|off)",
+        fun: cmd_set,
+        completer: Some(Completer::BoolFlag),
+    },
+    TypableCommand {
+        name: "bookmark",
+        aliases: &["bm"],
+        doc: "toggle the current content as a bookmark",
+        fun: cmd_bookmark,
+        completer: None,
+    },
+    TypableCommand {
+        name: "snippet",
+        aliases: &[],
+        doc: "insert the snippet bound to a key",
+        fun: cmd_snippet,
+        completer: Some(Completer::SnippetKey),
+    },
+    TypableCommand {
+        name: "isolation",
+        aliases: &[],
+        doc: "toggle isolated execution (on|off)",
+        fun: cmd_isolation,
+        completer: Some(Completer::BoolFlag),
+    },
+    TypableCommand {
+        name: "quit",
+        aliases: &["q"],
+        doc: "close pipr",
+        fun: cmd_quit,
+        completer: None,
+    },
+    TypableCommand {
+        name: "reload-config",
+        aliases: &["rl"],
+        doc: "reload pipr.toml and rebuild caches derived from it",
+        fun: cmd_reload_config,
+        completer: None,
+    },
+];
+
+/// Look up a command by name or alias.
+pub fn lookup(name: &str) -> Option<&'static TypableCommand> {
+    COMMANDS.iter().find(|command| command.matches(name))
+}
+
+/// Parse a command-line into a name and its whitespace-separated arguments and
+/// run the matching command. The leading `:` must already be stripped.
+pub fn dispatch(app: &mut App, line: &str) -> Result<(), failure::Error> {
+    let mut parts = line.split_whitespace();
+    let name = match parts.next() {
+        Some(name) => name,
+        None => return Ok(()),
+    };
+    let args = parts.collect::<Vec<&str>>();
+    match lookup(name) {
+        Some(command) => (command.fun)(app, &args),
+        None => Err(failure::err_msg(format!("unknown command ':{}'", name))),
+    }
+}
+
+fn parse_bool(value: Option<&&str>) -> Result<bool, failure::Error> {
+    match value {
+        Some(&"on") => Ok(true),
+        Some(&"off") => Ok(false),
+        _ => Err(failure::err_msg("expected 'on' or 'off'")),
+    }
+}
+
+fn cmd_write(app: &mut App, args: &[&str]) -> Result<(), failure::Error> {
+    let path = args.first().ok_or_else(|| failure::err_msg(":write expects a filename"))?;
+    File::create(path)?.write_all(app.input_state.content_str().as_bytes())?;
+    Ok(())
+}
+
+fn cmd_read(app: &mut App, args: &[&str]) -> Result<(), failure::Error> {
+    let path = args.first().ok_or_else(|| failure::err_msg(":read expects a filename"))?;
+    let mut buffer = String::new();
+    File::open(path)?.read_to_string(&mut buffer)?;
+    app.input_state.set_content(buffer.lines().map(String::from).collect());
+    Ok(())
+}
+
+fn cmd_set(app: &mut App, args: &[&str]) -> Result<(), failure::Error> {
+    let option = args.first().ok_or_else(|| failure::err_msg(":set expects an option"))?;
+    let value = parse_bool(args.get(1))?;
+    match *option {
+        "autoeval" => app.autoeval_mode = value,
+        "paranoid" => app.paranoid_history_mode = value,
+        other => return Err(failure::err_msg(format!("unknown option '{}'", other))),
+    }
+    Ok(())
+}
+
+fn cmd_bookmark(app: &mut App, _args: &[&str]) -> Result<(), failure::Error> {
+    app.bookmarks.toggle_entry(app.input_state.content_to_commandentry());
+    Ok(())
+}
+
+fn cmd_snippet(app: &mut App, args: &[&str]) -> Result<(), failure::Error> {
+    let key = args
+        .first()
+        .and_then(|key| key.chars().next())
+        .ok_or_else(|| failure::err_msg(":snippet expects a key"))?;
+    let snippet = app
+        .config
+        .snippets
+        .get(&key)
+        .ok_or_else(|| failure::err_msg(format!("no snippet bound to '{}'", key)))?
+        .clone();
+    app.input_state.insert_at_cursor(&snippet.text);
+    app.input_state.cursor_col += snippet.cursor_offset;
+    Ok(())
+}
+
+fn cmd_isolation(app: &mut App, args: &[&str]) -> Result<(), failure::Error> {
+    app.set_isolation_enabled(parse_bool(args.first())?);
+    Ok(())
+}
+
+fn cmd_quit(app: &mut App, _args: &[&str]) -> Result<(), failure::Error> {
+    app.set_should_quit();
+    Ok(())
+}
+
+fn cmd_reload_config(app: &mut App, _args: &[&str]) -> Result<(), failure::Error> {
+    app.reload_config();
+    Ok(())
+}
+
+// `Completer::options` and every `cmd_*`/`dispatch` success path take an
+// `&App`/`&mut App`, which this file has no constructor for (`App::new`
+// wires up the execution handler, config, bookmarks, history and plugins),
+// so only the App-independent logic below is covered: name/alias resolution
+// and `parse_bool`'s on/off/error cases.
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matches_checks_name_and_aliases() {
+        let write = lookup("write").unwrap();
+        assert!(write.matches("write"));
+        assert!(write.matches("w"));
+        assert!(!write.matches("write "));
+        assert!(!write.matches("read"));
+    }
+
+    #[test]
+    fn lookup_resolves_by_name_or_alias() {
+        assert_eq!(lookup("quit").unwrap().name, "quit");
+        assert_eq!(lookup("q").unwrap().name, "quit");
+        assert_eq!(lookup("reload-config").unwrap().name, "reload-config");
+        assert_eq!(lookup("rl").unwrap().name, "reload-config");
+    }
+
+    #[test]
+    fn lookup_returns_none_for_unknown_name() {
+        assert!(lookup("nonexistent").is_none());
+    }
+
+    #[test]
+    fn parse_bool_accepts_on_and_off() {
+        assert_eq!(parse_bool(Some(&"on")).unwrap(), true);
+        assert_eq!(parse_bool(Some(&"off")).unwrap(), false);
+    }
+
+    #[test]
+    fn parse_bool_rejects_anything_else() {
+        assert!(parse_bool(Some(&"yes")).is_err());
+        assert!(parse_bool(None).is_err());
+    }
+}
@@ -23,7 +23,9 @@ pub mod command_evaluation;
 pub mod command_template;
 pub mod commandlist;
 pub mod lineeditor;
+pub mod pipeline;
 pub mod pipr_config;
+pub mod plugins;
 pub mod snippets;
 pub mod ui;
 pub mod util;
@@ -70,9 +72,15 @@ async fn main() -> Result<(), failure::Error> {
     let bookmarks = CommandList::load_from_file(config_path.join("bookmarks"), None);
     let history = CommandList::load_from_file(config_path.join("history"), Some(config.history_size));
 
+    let (plugins, plugin_failures) = plugins::PluginRegistry::load(&config.plugins);
+
     // create app and set default
 
-    let mut app = App::new(execution_handler, args.raw_mode, config.clone(), bookmarks, history);
+    let mut app = App::new(execution_handler, args.raw_mode, config.clone(), bookmarks, history, plugins);
+
+    for failure in plugin_failures {
+        app.set_status(failure);
+    }
 
     if let Some(default_value) = args.default_content {
         app.input_state.set_content(default_value.lines().map_into().collect());
@@ -185,6 +193,21 @@ async fn run_app<W: Write>(mut app: &mut App, mut output_stream: W) -> Result<()
                 break;
             }
 
+            if let Some(diagnostics) = app.plugins.poll_lint() {
+                app.lint_diagnostics = diagnostics;
+                break;
+            }
+
+            if let Some(view) = app.pipeline_view.as_mut() {
+                if view.poll() {
+                    break;
+                }
+            }
+
+            for message in app.plugins.take_status_messages() {
+                app.set_status(message);
+            }
+
             if let Ok(true) = event::poll(std::time::Duration::from_millis(100)) {
                 match event::read()? {
                     CEvent::Resize(_, _) => break,
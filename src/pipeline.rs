@@ -0,0 +1,270 @@
+/// Split `input` into ordered pipeline stages on every top-level `|`. A `|`
+/// inside single or double quotes does not split, and a `||` (logical OR) is
+/// kept intact within its stage. Each returned stage is trimmed of surrounding
+/// whitespace; an input without a pipe yields a single stage.
+pub fn split_pipeline_stages(input: &str) -> Vec<String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut stages = Vec::new();
+    let mut current = String::new();
+    let mut in_single = false;
+    let mut in_double = false;
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\'' if !in_double => {
+                in_single = !in_single;
+                current.push(c);
+            }
+            '"' if !in_single => {
+                in_double = !in_double;
+                current.push(c);
+            }
+            '|' if !in_single && !in_double => {
+                if chars.get(i + 1) == Some(&'|') {
+                    // logical OR, part of the current stage rather than a split point
+                    current.push_str("||");
+                    i += 2;
+                    continue;
+                }
+                stages.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+        i += 1;
+    }
+    stages.push(current.trim().to_string());
+    stages
+}
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+
+/// The captured result of evaluating a single pipeline stage.
+#[derive(Debug, Clone)]
+pub struct StageResult {
+    pub stage_index: usize,
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: Option<i32>,
+}
+
+/// Backs the per-stage pipeline view. Holds the parsed stages and the results
+/// collected so far, plus a movable "cut point" cursor selecting which
+/// stage's output the UI shows. Evaluation runs on a background thread (see
+/// [`PipelineView::evaluate`]) and results are picked up progressively by
+/// polling [`PipelineView::poll`] from the same loop that drives
+/// `execution_handler.poll_output()`, so a slow stage never blocks the TUI.
+#[derive(Debug, Default)]
+pub struct PipelineView {
+    pub stages: Vec<String>,
+    pub results: Vec<StageResult>,
+    pub cut_point: usize,
+    epoch: Arc<AtomicU64>,
+    result_rx: Option<Receiver<(u64, StageResult)>>,
+}
+
+impl PipelineView {
+    /// Build a view for `input`, starting the cut point at the final stage.
+    pub fn new(input: &str) -> PipelineView {
+        let stages = split_pipeline_stages(input);
+        let cut_point = stages.len().saturating_sub(1);
+        PipelineView {
+            stages,
+            results: Vec::new(),
+            cut_point,
+            epoch: Arc::new(AtomicU64::new(0)),
+            result_rx: None,
+        }
+    }
+
+    /// Record a stage result as it becomes available.
+    pub fn push_result(&mut self, result: StageResult) {
+        self.results.push(result);
+    }
+
+    /// Kick off evaluation of the pipeline one stage at a time on a background
+    /// thread, feeding the captured stdout of each stage in as the stdin of
+    /// the next. `eval_environment` is applied per stage; `isolation_mounts`
+    /// mirrors the read-only bind mounts the rest of the app isolates command
+    /// execution with (`None` only when isolation is turned off, via
+    /// `--no-isolation` or `:isolation off`) so a stage runs under the same
+    /// sandbox as everything else, never raw on the host while isolation is on.
+    /// Evaluation stops early if a stage exits non-zero, so the user can
+    /// inspect where the pipeline broke. Call [`PipelineView::poll`] on the
+    /// main loop to pick up results as they arrive.
+    pub fn evaluate(&mut self, eval_environment: Vec<(String, String)>, isolation_mounts: Option<Vec<(String, String)>>) {
+        self.results.clear();
+        let epoch = self.epoch.fetch_add(1, Ordering::SeqCst) + 1;
+        let stages = self.stages.clone();
+        let (tx, rx) = mpsc::channel();
+        self.result_rx = Some(rx);
+
+        thread::spawn(move || {
+            let mut stdin_data = Vec::new();
+            for (stage_index, stage) in stages.iter().enumerate() {
+                let result = run_stage(stage, &stdin_data, &eval_environment, isolation_mounts.as_deref());
+                let failed = result.exit_code.map(|code| code != 0).unwrap_or(true);
+                stdin_data = result.stdout.clone().into_bytes();
+                if tx.send((epoch, StageResult { stage_index, ..result })).is_err() {
+                    return;
+                }
+                if failed {
+                    return;
+                }
+            }
+        });
+    }
+
+    /// Drain any results that have arrived from the background evaluation
+    /// kicked off by [`PipelineView::evaluate`], discarding any belonging to a
+    /// superseded (stale) run. Returns whether a new result was appended, so
+    /// the caller knows whether to redraw.
+    pub fn poll(&mut self) -> bool {
+        let rx = match self.result_rx.as_ref() {
+            Some(rx) => rx,
+            None => return false,
+        };
+        let current_epoch = self.epoch.load(Ordering::SeqCst);
+        let mut updated = false;
+        while let Ok((epoch, result)) = rx.try_recv() {
+            if epoch == current_epoch {
+                self.push_result(result);
+                updated = true;
+            }
+        }
+        updated
+    }
+
+    /// Move the cut point one stage towards the start of the pipeline.
+    pub fn move_cut_up(&mut self) {
+        self.cut_point = self.cut_point.saturating_sub(1);
+    }
+
+    /// Move the cut point one stage towards the end of the pipeline.
+    pub fn move_cut_down(&mut self) {
+        if self.cut_point + 1 < self.stages.len() {
+            self.cut_point += 1;
+        }
+    }
+
+    /// The result at the current cut point, if it has been evaluated yet.
+    pub fn output_at_cut(&self) -> Option<&StageResult> {
+        self.results.iter().find(|result| result.stage_index == self.cut_point)
+    }
+}
+
+/// Build the `bwrap` invocation the rest of the app isolates execution with,
+/// from the same `isolation_mounts_readonly` config the main command
+/// evaluator uses, so a pipeline stage never runs unsandboxed just because it
+/// went through this view instead of the normal command line.
+fn isolate(mounts: &[(String, String)], stage: &str) -> Command {
+    let mut command = Command::new("bwrap");
+    command.arg("--unshare-all").arg("--die-with-parent");
+    for (on_host, in_isolated) in mounts {
+        command.arg("--ro-bind").arg(on_host).arg(in_isolated);
+    }
+    command.arg("--dev").arg("/dev").arg("--proc").arg("/proc");
+    command.arg("--").arg("sh").arg("-c").arg(stage);
+    command
+}
+
+/// Run a single stage, writing `stdin_data` to its stdin and capturing its
+/// output. Runs under `bwrap` when `isolation_mounts` is `Some`, matching the
+/// sandboxing the rest of the app applies; only with isolation turned off
+/// (`--no-isolation` or `:isolation off`) does it run raw via `sh -c`. `stage_index` is filled in by the caller.
+fn run_stage(stage: &str, stdin_data: &[u8], eval_environment: &[(String, String)], isolation_mounts: Option<&[(String, String)]>) -> StageResult {
+    let mut command = match isolation_mounts {
+        Some(mounts) => isolate(mounts, stage),
+        None => {
+            let mut command = Command::new("sh");
+            command.arg("-c").arg(stage);
+            command
+        }
+    };
+    let spawned = command
+        .envs(eval_environment.iter().map(|(key, value)| (key, value)))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(err) => {
+            return StageResult {
+                stage_index: 0,
+                stdout: String::new(),
+                stderr: err.to_string(),
+                exit_code: None,
+            };
+        }
+    };
+
+    // Write stdin on its own thread, concurrently with `wait_with_output`
+    // draining stdout/stderr below. Writing it inline first would deadlock
+    // once `stdin_data` exceeds the OS pipe buffer and the child also blocks
+    // producing output before it's done reading stdin (e.g. `cat`, `tee`).
+    if let Some(mut stdin) = child.stdin.take() {
+        let stdin_data = stdin_data.to_vec();
+        thread::spawn(move || {
+            let _ = stdin.write_all(&stdin_data);
+        });
+    }
+
+    match child.wait_with_output() {
+        Ok(output) => StageResult {
+            stage_index: 0,
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            exit_code: output.status.code(),
+        },
+        Err(err) => StageResult {
+            stage_index: 0,
+            stdout: String::new(),
+            stderr: err.to_string(),
+            exit_code: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn input_without_pipe_is_a_single_stage() {
+        assert_eq!(split_pipeline_stages("grep foo"), vec!["grep foo"]);
+    }
+
+    #[test]
+    fn splits_on_top_level_pipes_and_trims() {
+        assert_eq!(
+            split_pipeline_stages("cat f |  grep foo | wc -l"),
+            vec!["cat f", "grep foo", "wc -l"]
+        );
+    }
+
+    #[test]
+    fn does_not_split_inside_quotes() {
+        assert_eq!(
+            split_pipeline_stages("awk '{print | \"sort\"}' | wc -l"),
+            vec!["awk '{print | \"sort\"}'", "wc -l"]
+        );
+        assert_eq!(split_pipeline_stages("echo \"a | b\""), vec!["echo \"a | b\""]);
+    }
+
+    #[test]
+    fn keeps_logical_or_intact() {
+        assert_eq!(
+            split_pipeline_stages("false || true | cat"),
+            vec!["false || true", "cat"]
+        );
+    }
+}
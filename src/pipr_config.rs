@@ -20,6 +20,10 @@ const DEFAULT_CONFIG: &str = "
 # Show the help-sidebar by default
 show_help = true
 
+# External plugin executables spoken to over newline-delimited JSON-RPC on stdin/stdout.
+# Each plugin may declare autocomplete, lint, transform and snippet_source hooks.
+# plugins = ['/path/to/my-pipr-plugin']
+
 # directories mounted into the isolated environment.
 # Syntax: '<on_host>:<in_isolated>'
 isolation_mounts_readonly = ['/lib:/lib', '/usr:/usr', '/lib64:/lib64', '/bin:/bin', '/etc:/etc']
@@ -31,6 +35,7 @@ pub struct PiprConfig {
     pub finish_hook: Option<String>,
     pub show_help: bool,
     pub isolation_mounts_readonly: Vec<(String, String)>,
+    pub plugins: Vec<String>,
 }
 
 impl PiprConfig {
@@ -60,6 +65,7 @@ impl PiprConfig {
                     "/etc:/etc".into(),
                 ]),
             ),
+            plugins: settings.get::<Vec<String>>("plugins").unwrap_or_default(),
         }
     }
 }
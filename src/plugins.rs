@@ -0,0 +1,334 @@
+use serde::Deserialize;
+use serde_json::json;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// How long to wait for a plugin's response before giving up and disabling it.
+/// Autocomplete requests run inline on the key-handling path, so a hung plugin
+/// must never block the TUI for longer than this.
+const RESPONSE_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// A hook a plugin can declare support for in its `config` response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginHook {
+    Autocomplete,
+    Lint,
+    Transform,
+    SnippetSource,
+}
+
+/// The `config` response a plugin sends on startup, describing itself.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginConfig {
+    pub name: String,
+    #[serde(default)]
+    pub hooks: Vec<PluginHook>,
+}
+
+/// A single diagnostic returned by a `lint` hook.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Diagnostic {
+    pub range: (usize, usize),
+    pub message: String,
+}
+
+/// The generic JSON-RPC response envelope. Either `result` or `error` is set.
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    #[serde(default)]
+    id: Option<u64>,
+    #[serde(default)]
+    result: Option<serde_json::Value>,
+    #[serde(default)]
+    error: Option<serde_json::Value>,
+}
+
+/// A single plugin process. Stdin is kept open across requests so the child
+/// stays alive; its stdout is drained on a dedicated reader thread and lines
+/// are forwarded over a channel, so a slow or hung plugin can be abandoned
+/// with a timeout instead of blocking the caller. Responses are correlated to
+/// requests by a monotonically incrementing `id`, just like a long-running
+/// language server.
+#[derive(Debug)]
+pub struct Plugin {
+    pub config: PluginConfig,
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+    next_id: u64,
+}
+
+impl Plugin {
+    /// Spawn the plugin executable, perform the initial `config` handshake and
+    /// return the live process. Returns `None` if the process can not be
+    /// spawned or the handshake fails (malformed JSON, early exit, timeout);
+    /// the caller disables the plugin in that case.
+    pub fn start(path: &str) -> Option<Plugin> {
+        let mut child = Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .ok()?;
+
+        let stdin = child.stdin.take()?;
+        let stdout = child.stdout.take()?;
+
+        let (tx, lines) = mpsc::channel();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(stdout);
+            loop {
+                let mut line = String::new();
+                match reader.read_line(&mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {
+                        if tx.send(line).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        let mut plugin = Plugin {
+            config: PluginConfig {
+                name: path.to_string(),
+                hooks: Vec::new(),
+            },
+            child,
+            stdin,
+            lines,
+            next_id: 1,
+        };
+
+        let response = plugin.request("config", json!([]))?;
+        plugin.config = serde_json::from_value(response).ok()?;
+        Some(plugin)
+    }
+
+    /// Whether this plugin declared support for the given hook.
+    pub fn declares(&self, hook: PluginHook) -> bool {
+        self.config.hooks.contains(&hook)
+    }
+
+    /// Send a single request line and read back the matching response line,
+    /// giving up after [`RESPONSE_TIMEOUT`]. Returns the `result` value, or
+    /// `None` on any protocol error (timeout, closed pipe, malformed JSON,
+    /// error envelope) so the caller can disable the offending plugin.
+    fn request(&mut self, method: &str, params: serde_json::Value) -> Option<serde_json::Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let request = json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": id });
+        writeln!(self.stdin, "{}", request).ok()?;
+        self.stdin.flush().ok()?;
+
+        // read lines until the response with our id arrives or the timeout
+        // elapses, skipping any notifications the plugin emits in between.
+        loop {
+            let line = match self.lines.recv_timeout(RESPONSE_TIMEOUT) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => return None,
+            };
+            let response: RpcResponse = match serde_json::from_str(&line) {
+                Ok(response) => response,
+                Err(_) => return None,
+            };
+            if response.id != Some(id) {
+                continue;
+            }
+            if response.error.is_some() {
+                return None;
+            }
+            return response.result;
+        }
+    }
+
+    /// Ask an `autocomplete` plugin for completion candidates at the cursor.
+    pub fn complete(&mut self, line: &str, cursor_col: usize, word: &str) -> Option<Vec<String>> {
+        let params = json!({ "line": line, "cursor_col": cursor_col, "word": word });
+        let result = self.request("complete", params)?;
+        serde_json::from_value(result).ok()
+    }
+
+    /// Ask a `lint` plugin to lint the current content.
+    pub fn lint(&mut self, content: &str) -> Option<Vec<Diagnostic>> {
+        let result = self.request("lint", json!({ "content": content }))?;
+        serde_json::from_value(result).ok()
+    }
+}
+
+impl Drop for Plugin {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Run every plugin declaring `hook` against it, via `call`, collecting
+/// results into `out` and dropping (with a status message) any plugin whose
+/// call fails. Shared by the synchronous `autocomplete` path and the
+/// background `lint` path so both disable misbehaving plugins the same way.
+fn fan_out<T>(
+    plugins: &Mutex<Vec<Plugin>>,
+    hook: PluginHook,
+    hook_name: &str,
+    out: &mut Vec<T>,
+    mut call: impl FnMut(&mut Plugin) -> Option<Vec<T>>,
+) -> Vec<String> {
+    let mut disabled = Vec::new();
+    plugins.lock().unwrap().retain_mut(|plugin| {
+        if !plugin.declares(hook) {
+            return true;
+        }
+        match call(plugin) {
+            Some(found) => {
+                out.extend(found);
+                true
+            }
+            None => {
+                disabled.push(format!(
+                    "plugin '{}' stopped responding to {} and was disabled",
+                    plugin.config.name, hook_name
+                ));
+                false
+            }
+        }
+    });
+    disabled
+}
+
+/// The content of the most recently queued `request_lint` call, along with
+/// its epoch, guarded by a condvar so the lint worker thread can sleep
+/// between requests instead of polling. A new request simply overwrites the
+/// pending one, so fast typing never queues up more than the single
+/// in-flight-or-next lint pass.
+type PendingLint = Arc<(Mutex<Option<(u64, String)>>, Condvar)>;
+
+/// Holds every live plugin and fans requests out to the ones that declared the
+/// relevant hook. Plugins that misbehave are dropped from the registry, with a
+/// status message queued so the UI can surface why completions or lint
+/// stopped coming from it. The plugin list is behind a mutex so `lint` can run
+/// on the single long-lived worker thread spawned in [`PluginRegistry::load`]
+/// (see [`PluginRegistry::request_lint`]) without blocking `autocomplete`,
+/// which still runs inline on the Tab key.
+#[derive(Debug)]
+pub struct PluginRegistry {
+    plugins: Arc<Mutex<Vec<Plugin>>>,
+    status_messages: Arc<Mutex<Vec<String>>>,
+    lint_epoch: Arc<AtomicU64>,
+    pending_lint: PendingLint,
+    lint_rx: Receiver<(u64, Vec<Diagnostic>)>,
+}
+
+impl PluginRegistry {
+    /// Spawn every plugin listed in the config, skipping those that fail to
+    /// start, plus the single background thread that services `lint`
+    /// requests for the lifetime of the registry. A status message is left
+    /// for each plugin-start failure so the UI can surface it.
+    pub fn load(paths: &[String]) -> (PluginRegistry, Vec<String>) {
+        let mut plugins = Vec::new();
+        let mut failures = Vec::new();
+        for path in paths {
+            match Plugin::start(path) {
+                Some(plugin) => plugins.push(plugin),
+                None => failures.push(format!("failed to start plugin '{}'", path)),
+            }
+        }
+
+        let plugins = Arc::new(Mutex::new(plugins));
+        let status_messages = Arc::new(Mutex::new(Vec::new()));
+        let pending_lint: PendingLint = Arc::new((Mutex::new(None), Condvar::new()));
+        let (lint_tx, lint_rx) = mpsc::channel();
+
+        {
+            let plugins = Arc::clone(&plugins);
+            let status_messages = Arc::clone(&status_messages);
+            let pending_lint = Arc::clone(&pending_lint);
+            thread::spawn(move || loop {
+                let (epoch, content) = {
+                    let (lock, condvar) = &*pending_lint;
+                    let mut pending = lock.lock().unwrap();
+                    loop {
+                        match pending.take() {
+                            Some(request) => break request,
+                            None => pending = condvar.wait(pending).unwrap(),
+                        }
+                    }
+                };
+                let mut diagnostics = Vec::new();
+                let disabled = fan_out(&plugins, PluginHook::Lint, "lint", &mut diagnostics, |plugin| plugin.lint(&content));
+                if !disabled.is_empty() {
+                    status_messages.lock().unwrap().extend(disabled);
+                }
+                if lint_tx.send((epoch, diagnostics)).is_err() {
+                    return;
+                }
+            });
+        }
+
+        let registry = PluginRegistry {
+            plugins,
+            status_messages,
+            lint_epoch: Arc::new(AtomicU64::new(0)),
+            pending_lint,
+            lint_rx,
+        };
+        (registry, failures)
+    }
+
+    /// Merge the candidates of every `autocomplete` plugin into `candidates`.
+    /// A plugin that errors is disabled (removed from the registry). Runs
+    /// inline: it is only triggered by an explicit Tab press, not on every
+    /// keystroke, and contends with at most the single in-flight `lint` call
+    /// for the plugins mutex, so the bounded [`RESPONSE_TIMEOUT`] stall is
+    /// acceptable here.
+    pub fn autocomplete(&mut self, line: &str, cursor_col: usize, word: &str, candidates: &mut Vec<String>) {
+        let disabled = fan_out(&self.plugins, PluginHook::Autocomplete, "autocomplete", candidates, |plugin| {
+            plugin.complete(line, cursor_col, word)
+        });
+        self.queue_status_messages(disabled);
+    }
+
+    /// Queue `content` to be linted by the background worker spawned in
+    /// [`PluginRegistry::load`]. A request in flight or already queued is
+    /// superseded rather than piled up, so fast typing never spawns more
+    /// lint work than the worker can keep up with; pick up the result with
+    /// [`PluginRegistry::poll_lint`].
+    pub fn request_lint(&mut self, content: String) {
+        let epoch = self.lint_epoch.fetch_add(1, Ordering::SeqCst) + 1;
+        let (lock, condvar) = &*self.pending_lint;
+        *lock.lock().unwrap() = Some((epoch, content));
+        condvar.notify_one();
+    }
+
+    /// Non-blocking poll for the most recently completed [`request_lint`]
+    /// call. A result superseded by a later keystroke before it arrived is
+    /// silently dropped in favour of the newer request.
+    pub fn poll_lint(&mut self) -> Option<Vec<Diagnostic>> {
+        let mut latest = None;
+        while let Ok((epoch, diagnostics)) = self.lint_rx.try_recv() {
+            if epoch == self.lint_epoch.load(Ordering::SeqCst) {
+                latest = Some(diagnostics);
+            }
+        }
+        latest
+    }
+
+    /// Drain the "plugin disabled" messages queued since the last call, for
+    /// the caller to surface via [`App::set_status`](crate::App::set_status).
+    pub fn take_status_messages(&mut self) -> Vec<String> {
+        std::mem::take(&mut *self.status_messages.lock().unwrap())
+    }
+
+    fn queue_status_messages(&self, messages: Vec<String>) {
+        if !messages.is_empty() {
+            self.status_messages.lock().unwrap().extend(messages);
+        }
+    }
+}
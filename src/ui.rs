@@ -0,0 +1,194 @@
+use crate::app::app::App;
+use crate::app::fuzzy::{FuzzyEntry, FuzzySource};
+use crate::pipeline::PipelineView;
+use tui::backend::Backend;
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph};
+use tui::{Frame, Terminal};
+
+/// A `Rect` centered in `area`, `percent_x`/`percent_y` of its size. The usual
+/// tui-rs recipe for a floating modal overlay.
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(vertical[1])[1]
+}
+
+/// Draw a full frame: the input editor, the lint-diagnostics panel (when
+/// there are any), the status/command-palette line, and on top of those
+/// whichever modal overlay (fuzzy finder, pipeline view) is currently open.
+pub async fn draw_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<(), failure::Error> {
+    terminal.draw(|frame| {
+        let size = frame.size();
+        let diagnostics_height = if app.lint_diagnostics.is_empty() {
+            0
+        } else {
+            app.lint_diagnostics.len().min(3) as u16
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(diagnostics_height), Constraint::Length(1)].as_ref())
+            .split(size);
+
+        draw_input(frame, app, chunks[0]);
+        draw_lint_diagnostics(frame, app, chunks[1]);
+        match &app.command_line {
+            Some(command_line) => draw_command_palette(frame, command_line, chunks[2]),
+            None => draw_status_line(frame, app, chunks[2]),
+        }
+
+        if let Some(fuzzy_finder) = &app.fuzzy_finder {
+            let popup = centered_rect(60, 60, size);
+            frame.render_widget(Clear, popup);
+            draw_fuzzy_finder(frame, &fuzzy_finder.query, fuzzy_finder.source, &fuzzy_finder.results, fuzzy_finder.selected, popup);
+        }
+
+        if let Some(pipeline_view) = &app.pipeline_view {
+            let popup = centered_rect(80, 80, size);
+            frame.render_widget(Clear, popup);
+            draw_pipeline_view(frame, pipeline_view, popup);
+        }
+    })?;
+    Ok(())
+}
+
+fn draw_input<B: Backend>(frame: &mut Frame<B>, app: &App, area: Rect) {
+    let text = app.input_state.content_lines().join("\n");
+    let input = Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("pipr"));
+    frame.render_widget(input, area);
+}
+
+/// Render one line per lint diagnostic directly under the input. Capped at
+/// the handful reserved by `draw_app`'s layout so a chatty linter can't push
+/// the editor off screen.
+fn draw_lint_diagnostics<B: Backend>(frame: &mut Frame<B>, app: &App, area: Rect) {
+    if app.lint_diagnostics.is_empty() {
+        return;
+    }
+    let items: Vec<ListItem> = app
+        .lint_diagnostics
+        .iter()
+        .map(|diagnostic| ListItem::new(diagnostic.message.clone()).style(Style::default().fg(Color::Yellow)))
+        .collect();
+    frame.render_widget(List::new(items), area);
+}
+
+/// Render the `:`-prefixed command palette prompt, replacing the status line
+/// while it's open, with a visible cursor so the typed command is legible.
+fn draw_command_palette<B: Backend>(frame: &mut Frame<B>, command_line: &str, area: Rect) {
+    let prompt = Spans::from(vec![
+        Span::styled(":", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(command_line),
+        Span::styled("_", Style::default().add_modifier(Modifier::RAPID_BLINK)),
+    ]);
+    frame.render_widget(Paragraph::new(prompt), area);
+}
+
+/// Render the Ctrl-R fuzzy finder overlay: a bordered popup listing the
+/// scored results for `query`, each with its matched characters highlighted,
+/// and the currently selected entry picked out.
+fn draw_fuzzy_finder<B: Backend>(
+    frame: &mut Frame<B>,
+    query: &str,
+    source: FuzzySource,
+    results: &[FuzzyEntry],
+    selected: usize,
+    area: Rect,
+) {
+    let title = match source {
+        FuzzySource::History => format!("history: {}", query),
+        FuzzySource::Bookmarks => format!("bookmarks: {}", query),
+    };
+    let items: Vec<ListItem> = results
+        .iter()
+        .enumerate()
+        .map(|(idx, result)| {
+            let spans: Vec<Span> = result
+                .text
+                .chars()
+                .enumerate()
+                .map(|(char_idx, c)| {
+                    if result.indices.contains(&char_idx) {
+                        Span::styled(c.to_string(), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+                    } else {
+                        Span::raw(c.to_string())
+                    }
+                })
+                .collect();
+            let style = if idx == selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Spans::from(spans)).style(style)
+        })
+        .collect();
+    frame.render_widget(List::new(items).block(Block::default().borders(Borders::ALL).title(title)), area);
+}
+
+/// Render the F4 per-stage pipeline view: the stage list on the left, marking
+/// the movable cut point and which stages have an evaluated result yet (since
+/// evaluation happens progressively on a background thread), and the
+/// captured stdout/stderr at the cut point on the right.
+fn draw_pipeline_view<B: Backend>(frame: &mut Frame<B>, view: &PipelineView, area: Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)].as_ref())
+        .split(area);
+
+    let stage_items: Vec<ListItem> = view
+        .stages
+        .iter()
+        .enumerate()
+        .map(|(stage_index, stage)| {
+            let evaluated = view.results.iter().any(|result| result.stage_index == stage_index);
+            let marker = if evaluated { "✓" } else { "…" };
+            let style = if stage_index == view.cut_point {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            ListItem::new(format!("{} {}", marker, stage)).style(style)
+        })
+        .collect();
+    frame.render_widget(
+        List::new(stage_items).block(Block::default().borders(Borders::ALL).title("pipeline")),
+        columns[0],
+    );
+
+    let output_text = match view.output_at_cut() {
+        Some(result) if !result.stderr.is_empty() => format!("{}\n--- stderr ---\n{}", result.stdout, result.stderr),
+        Some(result) => result.stdout.clone(),
+        None => "(not evaluated yet)".to_string(),
+    };
+    frame.render_widget(
+        Paragraph::new(output_text).block(Block::default().borders(Borders::ALL).title("output at cut")),
+        columns[1],
+    );
+}
+
+fn draw_status_line<B: Backend>(frame: &mut Frame<B>, app: &App, area: Rect) {
+    let text = app.status_message.clone().unwrap_or_default();
+    frame.render_widget(Paragraph::new(text), area);
+}